@@ -0,0 +1,167 @@
+//! Full-text license identification.
+//!
+//! Matches an arbitrary block of text against the canonical license texts
+//! shipped with this crate (embedded from `spdx/license-list-data` by
+//! `update`) using word-shingle overlap, scored with the Sørensen–Dice
+//! coefficient. This is useful for classifying a `LICENSE` file whose
+//! header doesn't carry an SPDX id.
+
+use once_cell::sync::OnceCell;
+use std::collections::HashSet;
+
+/// The compressed `(id, text)` pairs produced by `update`, embedded at
+/// compile time.
+static LICENSE_TEXTS: &[u8] = include_bytes!("license_texts.bin.zst");
+
+/// Minimum Dice score for a candidate to be reported by [`identify`].
+const MATCH_THRESHOLD: f32 = 0.9;
+
+/// Size of the word n-grams ("shingles") compared between texts.
+const SHINGLE_SIZE: usize = 3;
+
+struct Shingled {
+    id: &'static str,
+    shingles: HashSet<String>,
+}
+
+static SHINGLED_LICENSES: OnceCell<Vec<Shingled>> = OnceCell::new();
+
+fn shingled_licenses() -> &'static [Shingled] {
+    SHINGLED_LICENSES
+        .get_or_init(|| {
+            let decompressed =
+                zstd::decode_all(LICENSE_TEXTS).expect("embedded license text blob is corrupt");
+            let texts: Vec<(String, String)> = bincode::deserialize(&decompressed)
+                .expect("embedded license text blob has an unexpected shape");
+
+            texts
+                .into_iter()
+                .map(|(id, text)| Shingled {
+                    id: Box::leak(id.into_boxed_str()),
+                    shingles: shingles(&normalize_license_text(&text), SHINGLE_SIZE),
+                })
+                .collect()
+        })
+        .as_slice()
+}
+
+/// Lowercases `text`, drops lines that look like copyright/attribution
+/// boilerplate, strips punctuation and collapses whitespace. Applied to both
+/// the query and each embedded license text before shingling.
+fn normalize_license_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for line in text.to_lowercase().lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("copyright")
+            || trimmed.starts_with("(c)")
+            || trimmed.starts_with('\u{a9}')
+        {
+            continue;
+        }
+
+        let mut last_was_space = true;
+        for ch in trimmed.chars() {
+            if ch.is_alphanumeric() {
+                out.push(ch);
+                last_was_space = false;
+            } else if !last_was_space {
+                out.push(' ');
+                last_was_space = true;
+            }
+        }
+        if !last_was_space {
+            out.push(' ');
+        }
+    }
+
+    out.trim().to_owned()
+}
+
+fn shingles(normalized: &str, n: usize) -> HashSet<String> {
+    let words: Vec<&str> = normalized.split_whitespace().collect();
+    let mut set = HashSet::new();
+
+    if words.len() < n {
+        if !words.is_empty() {
+            set.insert(words.join(" "));
+        }
+        return set;
+    }
+
+    for window in words.windows(n) {
+        set.insert(window.join(" "));
+    }
+
+    set
+}
+
+/// Sørensen–Dice coefficient: `2·|A∩B| / (|A|+|B|)`.
+fn dice(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+
+    let intersection = a.intersection(b).count();
+    (2.0 * intersection as f32) / (a.len() + b.len()) as f32
+}
+
+/// Identifies the SPDX license id that best matches `text`, along with its
+/// Dice score, or `None` if no embedded license scores at or above the
+/// match threshold (`0.9`).
+pub fn identify(text: &str) -> Option<(&'static str, f32)> {
+    let query = shingles(&normalize_license_text(text), SHINGLE_SIZE);
+
+    shingled_licenses()
+        .iter()
+        .map(|candidate| (candidate.id, dice(&query, &candidate.shingles)))
+        .filter(|(_, score)| *score >= MATCH_THRESHOLD)
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_strips_copyright_lines_and_punctuation() {
+        let text = "Copyright (c) 2024 Jane Doe\n\u{a9} 2024 Jane Doe\nPermission is hereby granted, free of charge!";
+        assert_eq!(
+            normalize_license_text(text),
+            "permission is hereby granted free of charge"
+        );
+    }
+
+    #[test]
+    fn shingles_short_input_falls_back_to_whole_string() {
+        let set = shingles("too short", 3);
+        assert_eq!(set.len(), 1);
+        assert!(set.contains("too short"));
+    }
+
+    #[test]
+    fn shingles_windows_over_words() {
+        let set = shingles("a b c d", 3);
+        assert_eq!(set.len(), 2);
+        assert!(set.contains("a b c"));
+        assert!(set.contains("b c d"));
+    }
+
+    #[test]
+    fn dice_of_identical_sets_is_one() {
+        let a = shingles("a b c d", 3);
+        assert_eq!(dice(&a, &a), 1.0);
+    }
+
+    #[test]
+    fn dice_of_disjoint_sets_is_zero() {
+        let a = shingles("a b c", 3);
+        let b = shingles("x y z", 3);
+        assert_eq!(dice(&a, &b), 0.0);
+    }
+
+    #[test]
+    fn dice_of_two_empty_sets_is_one() {
+        let empty = HashSet::new();
+        assert_eq!(dice(&empty, &empty), 1.0);
+    }
+}
@@ -0,0 +1,5 @@
+mod identify;
+
+pub use identify::identify;
+
+include!("identifiers.rs");
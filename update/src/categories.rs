@@ -0,0 +1,122 @@
+// Category classification for SPDX license ids, modeled on ScanCode's
+// `category` taxonomy. Checked in and hand-maintained like imprecise.rs;
+// extend the match below when upstream adds a license that shouldn't fall
+// through to `Unknown`.
+//
+// Unlike the old `is_copyleft` prefix check, this distinguishes
+// strong copyleft (GPL/AGPL-style: distributing it obligates you to share
+// the whole derivative work's source) from weak/file-level copyleft
+// (MPL/LGPL/CDDL/EPL-style: only modifications to the licensed files
+// themselves need to be shared).
+
+/// A coarse classification of what a license requires of a user,
+/// modeled on ScanCode's license categories.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Category {
+    Permissive,
+    /// Strong copyleft: GPL, AGPL and similar licenses that require
+    /// derivative works as a whole to be shared under the same terms.
+    Copyleft,
+    /// Weak/file-level copyleft: MPL, LGPL, CDDL, EPL and similar licenses
+    /// that only require modifications to the licensed files to be shared.
+    CopyleftLimited,
+    PublicDomain,
+    ProprietaryFree,
+    SourceAvailable,
+    Unknown,
+}
+
+impl Category {
+    /// Classifies an SPDX license id, defaulting to `Unknown` for anything
+    /// not covered by the table below.
+    pub fn classify(id: &str) -> Category {
+        match id {
+            _ if id.starts_with("AGPL-")
+                || id.starts_with("GPL-")
+                || id.starts_with("GFDL-")
+                || id.starts_with("OSL-")
+                || id.starts_with("CC-BY-SA-")
+                || id.starts_with("CC-BY-NC-SA-")
+                || id.starts_with("CECILL-")
+                || id == "Parity-6.0.0"
+                || id == "YPL-1.1" =>
+            {
+                Category::Copyleft
+            }
+
+            _ if id.starts_with("LGPL-")
+                || id.starts_with("MPL-")
+                || id.starts_with("CDDL-")
+                || id.starts_with("CPL-")
+                || id.starts_with("EPL-")
+                || id.starts_with("NPL-")
+                || id.starts_with("EUPL")
+                || id == "MS-PL"
+                || id == "MS-RL"
+                || id == "SISSL"
+                || id == "xinetd"
+                || id == "BSD-Protection" =>
+            {
+                Category::CopyleftLimited
+            }
+
+            "CC0-1.0" | "Unlicense" | "WTFPL" | "0BSD" => Category::PublicDomain,
+
+            _ if id.starts_with("SSPL-")
+                || id.starts_with("Elastic-")
+                || id.starts_with("BUSL-")
+                || id.starts_with("Parity-7.0.0")
+                || id.starts_with("CAL-")
+                || id.starts_with("Polyform-") =>
+            {
+                Category::SourceAvailable
+            }
+
+            "Commons-Clause" => Category::ProprietaryFree,
+
+            _ if id.starts_with("LicenseRef-") => Category::Unknown,
+
+            _ if id.starts_with("MIT")
+                || id.starts_with("BSD-")
+                || id.starts_with("Apache-")
+                || id.starts_with("BSL-")
+                || id == "ISC"
+                || id == "Zlib" =>
+            {
+                Category::Permissive
+            }
+
+            _ => Category::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_known_prefixes() {
+        assert_eq!(Category::classify("GPL-3.0-only"), Category::Copyleft);
+        assert_eq!(
+            Category::classify("LGPL-2.1-only"),
+            Category::CopyleftLimited
+        );
+        assert_eq!(Category::classify("CC0-1.0"), Category::PublicDomain);
+        assert_eq!(Category::classify("SSPL-1.0"), Category::SourceAvailable);
+        assert_eq!(
+            Category::classify("Commons-Clause"),
+            Category::ProprietaryFree
+        );
+        assert_eq!(Category::classify("MIT"), Category::Permissive);
+    }
+
+    #[test]
+    fn licenseref_is_unknown_rather_than_guessed() {
+        assert_eq!(
+            Category::classify("LicenseRef-my-company-internal"),
+            Category::Unknown
+        );
+        assert_eq!(Category::classify("Some-Made-Up-Id"), Category::Unknown);
+    }
+}
@@ -0,0 +1,29 @@
+// Hand-maintained fallback for which SPDX license ids a given exception is
+// commonly applied with. spdx/license-list-data doesn't (yet) publish this
+// relationship as structured data, so `update` merges whatever it finds in
+// an exception's `relatedLicenses` detail field (if upstream ever adds one)
+// with this table. Extend the match below for new exceptions we want to be
+// able to validate `License WITH Exception` expressions against.
+pub fn applies_to(exception_id: &str) -> &'static [&'static str] {
+    match exception_id {
+        "Classpath-exception-2.0" => &["GPL-2.0-only", "GPL-2.0-or-later"],
+        "LLVM-exception" => &["Apache-2.0"],
+        "GCC-exception-2.0" => &["GPL-2.0-only", "GPL-2.0-or-later"],
+        "GCC-exception-3.1" => &["GPL-3.0-only", "GPL-3.0-or-later"],
+        "Autoconf-exception-2.0" => &["GPL-2.0-only", "GPL-2.0-or-later"],
+        "Autoconf-exception-3.0" => &["GPL-3.0-only", "GPL-3.0-or-later"],
+        "Bison-exception-2.2" => &[
+            "GPL-2.0-only",
+            "GPL-2.0-or-later",
+            "GPL-3.0-only",
+            "GPL-3.0-or-later",
+        ],
+        "Qwt-exception-1.0" => &["LGPL-2.1-only", "LGPL-2.1-or-later"],
+        "OpenJDK-assembly-exception-1.0" => &["GPL-2.0-only", "GPL-2.0-or-later"],
+        "freertos-exception-2.0" => &["GPL-2.0-only", "GPL-2.0-or-later"],
+        "u-boot-exception-2.0" => &["GPL-2.0-only", "GPL-2.0-or-later"],
+        "Linux-syscall-note" => &["GPL-2.0-only", "GPL-2.0-or-later"],
+        "WxWindows-exception-3.1" => &["LGPL-2.0-only", "LGPL-2.0-or-later"],
+        _ => &[],
+    }
+}
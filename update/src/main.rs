@@ -1,36 +1,144 @@
 use failure::bail;
+use serde::{de::DeserializeOwned, Deserialize};
 use serde_json::{map, Value};
-use std::{
-    env,
-    io::{self, Write},
-    process,
-};
+use std::{env, io::Write, process};
 
 type Map = map::Map<String, Value>;
 
 type Result<T> = std::result::Result<T, failure::Error>;
 
-fn download<F>(uri: &str, mut action: F, debug: bool) -> Result<()>
-where
-    F: FnMut(Map) -> Result<()>,
-{
-    let json: Value = reqwest::blocking::get(uri)?.json()?;
-    let json = if let Value::Object(m) = json {
-        m
-    } else {
-        bail!("Malformed JSON: {:?}", json)
-    };
+/// The `licenses.json`/`exceptions.json` top-level shape. Both endpoints
+/// share this shape (each just leaves its other array empty), so one struct
+/// covers both instead of hand-walking a `serde_json::Value`.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct LicenseList {
+    license_list_version: String,
+    #[serde(default)]
+    release_date: String,
+    #[serde(default)]
+    licenses: Vec<License>,
+    #[serde(default)]
+    exceptions: Vec<Exception>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct License {
+    license_id: String,
+    name: String,
+    #[serde(default)]
+    is_osi_approved: bool,
+    #[serde(default)]
+    is_fsf_libre: bool,
+    #[serde(default)]
+    is_deprecated_license_id: bool,
+    #[serde(default)]
+    see_also: Vec<String>,
+    reference: Option<String>,
+    details_url: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Exception {
+    license_exception_id: String,
+    name: Option<String>,
+    #[serde(default)]
+    is_deprecated_license_id: bool,
+    #[serde(default)]
+    see_also: Vec<String>,
+    reference: Option<String>,
+    details_url: Option<String>,
+}
+
+/// Mirrors of the structs above with `deny_unknown_fields`, used only to
+/// validate upstream's response in `--strict` mode. We don't use these for
+/// the actual data: a forward-compatible parse shouldn't start failing the
+/// moment upstream adds a field, but CI running with `--strict` should tell
+/// us loudly when that happens so we can decide whether to capture it.
+mod strict {
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub struct LicenseList {
+        #[allow(dead_code)]
+        pub license_list_version: String,
+        #[serde(default)]
+        #[allow(dead_code)]
+        pub release_date: String,
+        #[serde(default)]
+        #[allow(dead_code)]
+        pub licenses: Vec<License>,
+        #[serde(default)]
+        #[allow(dead_code)]
+        pub exceptions: Vec<Exception>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub struct License {
+        #[allow(dead_code)]
+        pub license_id: String,
+        #[allow(dead_code)]
+        pub name: String,
+        #[serde(default)]
+        #[allow(dead_code)]
+        pub is_osi_approved: bool,
+        #[serde(default)]
+        #[allow(dead_code)]
+        pub is_fsf_libre: bool,
+        #[serde(default)]
+        #[allow(dead_code)]
+        pub is_deprecated_license_id: bool,
+        #[serde(default)]
+        #[allow(dead_code)]
+        pub see_also: Vec<String>,
+        #[allow(dead_code)]
+        pub reference: Option<String>,
+        #[allow(dead_code)]
+        pub details_url: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase", deny_unknown_fields)]
+    pub struct Exception {
+        #[allow(dead_code)]
+        pub license_exception_id: String,
+        #[allow(dead_code)]
+        pub name: Option<String>,
+        #[serde(default)]
+        #[allow(dead_code)]
+        pub is_deprecated_license_id: bool,
+        #[serde(default)]
+        #[allow(dead_code)]
+        pub see_also: Vec<String>,
+        #[allow(dead_code)]
+        pub reference: Option<String>,
+        #[allow(dead_code)]
+        pub details_url: Option<String>,
+    }
+}
+
+/// Fetches and deserializes `uri` into `T`. In `strict` mode, also parses the
+/// response against the `deny_unknown_fields` mirror in [`strict`] purely to
+/// detect upstream schema drift; that parse's result is discarded.
+fn download<T: DeserializeOwned>(uri: &str, strict: bool, debug: bool) -> Result<T> {
+    let body = reqwest::blocking::get(uri)?.text()?;
+
+    if strict {
+        serde_json::from_str::<strict::LicenseList>(&body)
+            .map_err(|e| failure::format_err!("{} has fields we don't capture: {}", uri, e))?;
+    }
+
+    let parsed = serde_json::from_str(&body)?;
 
     if debug {
-        writeln!(io::stderr(), "#json == {}", json.len())?;
-        writeln!(
-            io::stderr(),
-            "License list version {}",
-            get(&json, "licenseListVersion")?
-        )?;
+        eprintln!("fetched {}", uri);
     }
 
-    action(json)
+    Ok(parsed)
 }
 
 fn get<'a>(m: &'a Map, k: &str) -> Result<&'a Value> {
@@ -40,33 +148,17 @@ fn get<'a>(m: &'a Map, k: &str) -> Result<&'a Value> {
 
 const IMPRECISE: &str = include_str!("imprecise.rs");
 
-fn is_copyleft(license: &str) -> bool {
-    // Copyleft licenses are determined from
-    // https://www.gnu.org/licenses/license-list.en.html
-    // and no distinction is made between "weak" and "strong"
-    // copyleft, for simplicity
-    license.starts_with("AGPL-")
-        || license.starts_with("CC-BY-NC-SA-")
-        || license.starts_with("CC-BY-SA-")
-        || license.starts_with("CECILL-")
-        || license.starts_with("CPL-")
-        || license.starts_with("CDDL-")
-        || license.starts_with("EUPL")
-        || license.starts_with("GFDL-")
-        || license.starts_with("GPL-")
-        || license.starts_with("LGPL-")
-        || license.starts_with("MPL-")
-        || license.starts_with("NPL-")
-        || license.starts_with("OSL-")
-        || license == "BSD-Protection"
-        || license == "MS-PL"
-        || license == "MS-RL"
-        //|| license == "OpenSSL" <- this one seems to be debated, but not really copyleft
-        || license == "Parity-6.0.0"
-        || license == "SISSL"
-        || license == "xinetd"
-        || license == "YPL-1.1"
-}
+// Brings `Category` and `Category::classify` into scope for the generator
+// itself, so it can pick a category for each license while also splicing
+// this file's text into identifiers.rs for downstream consumers.
+include!("categories.rs");
+
+const CATEGORIES: &str = include_str!("categories.rs");
+
+// Hand-maintained exception -> applicable-license fallback table, merged
+// with whatever `fetch_exception_applies_to` finds in upstream's own
+// per-exception detail JSON.
+include!("exception_applies_to.rs");
 
 fn is_gnu(license: &str) -> bool {
     license.starts_with("AGPL-")
@@ -75,14 +167,143 @@ fn is_gnu(license: &str) -> bool {
         || license.starts_with("LGPL-")
 }
 
+/// Fetches the `licenseText` (falling back to `standardLicenseTemplate`) for
+/// every license id from its `detailsUrl` (falling back to hand-building
+/// `json/details/<id>.json` when upstream didn't provide one), skipping ids
+/// for which upstream has no text (e.g. our synthetic `NOASSERTION`).
+fn fetch_license_texts(
+    tag: &str,
+    ids: &[(String, Option<String>)],
+    debug: bool,
+) -> Result<Vec<(String, String)>> {
+    let client = reqwest::blocking::Client::new();
+    let mut texts = Vec::with_capacity(ids.len());
+
+    for (id, details_url) in ids {
+        if id == "NOASSERTION" {
+            continue;
+        }
+
+        let uri = details_url.clone().unwrap_or_else(|| {
+            format!(
+                "https://raw.githubusercontent.com/spdx/license-list-data/{}/json/details/{}.json",
+                tag, id
+            )
+        });
+
+        let detail: Value = client.get(&uri).send()?.json()?;
+        let detail = if let Value::Object(m) = detail {
+            m
+        } else {
+            bail!("Malformed JSON: {:?}", detail)
+        };
+
+        let text = match get(&detail, "licenseText") {
+            Ok(Value::String(s)) => s.clone(),
+            _ => match get(&detail, "standardLicenseTemplate") {
+                Ok(Value::String(s)) => s.clone(),
+                _ => {
+                    if debug {
+                        eprintln!("no license text for {}, skipping", id);
+                    }
+                    continue;
+                }
+            },
+        };
+
+        texts.push((id.clone(), text));
+    }
+
+    Ok(texts)
+}
+
+/// Serializes and zstd-compresses `texts` to `src/license_texts.bin.zst`,
+/// which is embedded into the crate with `include_bytes!` and lazily
+/// decompressed once by the `identify` module at runtime.
+fn write_license_text_blob(texts: &[(String, String)], debug: bool) -> Result<()> {
+    let encoded = bincode::serialize(texts)
+        .map_err(|e| failure::format_err!("failed to serialize license texts: {}", e))?;
+    let compressed = zstd::encode_all(&encoded[..], 19)
+        .map_err(|e| failure::format_err!("failed to compress license texts: {}", e))?;
+
+    if debug {
+        eprintln!(
+            "license text blob: {} bytes raw, {} bytes compressed",
+            encoded.len(),
+            compressed.len()
+        );
+    }
+
+    std::fs::write("src/license_texts.bin.zst", compressed)?;
+    Ok(())
+}
+
+/// Fetches each exception's detail JSON (via its `detailsUrl`, falling back
+/// to hand-building `json/exceptions/<id>.json` when upstream didn't provide
+/// one) and determines which SPDX license ids it's commonly applied with,
+/// merging any `relatedLicenses` the detail JSON carries with the checked-in
+/// fallback table in `exception_applies_to.rs` (upstream mostly doesn't
+/// publish this relationship as structured data yet).
+fn fetch_exception_applies_to(
+    tag: &str,
+    ids: &[(String, Option<String>)],
+    debug: bool,
+) -> Result<Vec<(String, Vec<String>)>> {
+    let client = reqwest::blocking::Client::new();
+    let mut out = Vec::with_capacity(ids.len());
+
+    for (id, details_url) in ids {
+        let mut related: Vec<String> = applies_to(id).iter().map(|s| s.to_string()).collect();
+
+        let uri = details_url.clone().unwrap_or_else(|| {
+            format!(
+                "https://raw.githubusercontent.com/spdx/license-list-data/{}/json/exceptions/{}.json",
+                tag, id
+            )
+        });
+
+        match client.get(&uri).send() {
+            Ok(resp) if resp.status() == reqwest::StatusCode::NOT_FOUND => {
+                if debug {
+                    eprintln!("no exception detail for {}, using fallback table only", id);
+                }
+            }
+            Ok(resp) => match resp.json::<Value>() {
+                Ok(Value::Object(detail)) => {
+                    if let Some(Value::Array(from_detail)) = detail.get("relatedLicenses") {
+                        for lic in from_detail {
+                            if let Value::String(s) = lic {
+                                if !related.contains(s) {
+                                    related.push(s.clone());
+                                }
+                            }
+                        }
+                    }
+                }
+                Ok(other) => bail!("Malformed JSON for {}: {:?}", uri, other),
+                Err(e) => bail!("failed to parse exception detail {}: {}", uri, e),
+            },
+            Err(e) => bail!("failed to fetch {}: {}", uri, e),
+        }
+
+        out.push((id.clone(), related));
+    }
+
+    Ok(out)
+}
+
 fn real_main() -> Result<()> {
     let mut upstream_tag = None;
     let mut debug = false;
+    let mut strict = false;
     for e in env::args().skip(1) {
         match e.as_str() {
             "-d" => {
                 debug = true;
             }
+            "--strict" => {
+                strict = true;
+            }
             s if s.starts_with('v') => upstream_tag = Some(s.to_owned()),
             _ => bail!("Unknown option {:?}", e),
         }
@@ -128,111 +349,149 @@ pub const IS_GNU: u8 = 0x10;
         upstream_tag
     )?;
 
+    writeln!(identifiers, "{}", CATEGORIES)?;
+
     let licenses_json_uri = format!(
         "https://raw.githubusercontent.com/spdx/license-list-data/{}/json/licenses.json",
         upstream_tag
     );
 
-    download(
-        &licenses_json_uri,
-        |json| {
-            let licenses = get(&json, "licenses")?;
-            let licenses = if let Value::Array(ref v) = licenses {
-                v
-            } else {
-                bail!("Malformed JSON: {:?}", licenses)
-            };
-            eprintln!("#licenses == {}", licenses.len());
-
-            let mut v = vec![];
-            for lic in licenses.iter() {
-                let lic = if let Value::Object(ref m) = *lic {
-                    m
-                } else {
-                    bail!("Malformed JSON: {:?}", lic)
-                };
-                if debug {
-                    eprintln!("{:?},{:?}", get(lic, "licenseId"), get(lic, "name"));
-                }
+    let mut license_ids = Vec::new();
 
-                let lic_id = get(lic, "licenseId")?;
-                if let Value::String(id) = lic_id {
-                    let mut flags = String::with_capacity(100);
+    let license_list: LicenseList = download(&licenses_json_uri, strict, debug)?;
+    eprintln!("#licenses == {}", license_list.licenses.len());
 
-                    if let Ok(Value::Bool(val)) = get(lic, "isDeprecatedLicenseId") {
-                        if *val {
-                            flags.push_str("IS_DEPRECATED | ");
-                        }
-                    }
+    let mut v = vec![];
+    for lic in &license_list.licenses {
+        let id = lic.license_id.as_str();
+        if debug {
+            eprintln!("{:?},{:?}", id, lic.name);
+        }
 
-                    if let Ok(Value::Bool(val)) = get(lic, "isOsiApproved") {
-                        if *val {
-                            flags.push_str("IS_OSI_APPROVED | ");
-                        }
-                    }
+        let mut flags = String::with_capacity(100);
 
-                    if let Ok(Value::Bool(val)) = get(lic, "isFsfLibre") {
-                        if *val {
-                            flags.push_str("IS_FSF_LIBRE | ");
-                        }
-                    }
+        if lic.is_deprecated_license_id {
+            flags.push_str("IS_DEPRECATED | ");
+        }
 
-                    if is_copyleft(id) {
-                        flags.push_str("IS_COPYLEFT | ");
-                    }
+        if lic.is_osi_approved {
+            flags.push_str("IS_OSI_APPROVED | ");
+        }
 
-                    if is_gnu(id) {
-                        flags.push_str("IS_GNU | ");
-                    }
+        if lic.is_fsf_libre {
+            flags.push_str("IS_FSF_LIBRE | ");
+        }
 
-                    if flags.is_empty() {
-                        flags.push_str("0x0");
-                    } else {
-                        // Strip the trailing ` | `
-                        flags.truncate(flags.len() - 3);
-                    }
+        let category = Category::classify(id);
+        if matches!(category, Category::Copyleft | Category::CopyleftLimited) {
+            flags.push_str("IS_COPYLEFT | ");
+        }
 
-                    let full_name = if let Value::String(name) = get(lic, "name")? {
-                        name
-                    } else {
-                        id
-                    };
+        if is_gnu(id) {
+            flags.push_str("IS_GNU | ");
+        }
 
-                    // Add `-invariants` versions of the root GFDL-<version>
-                    // licenses so that they work slightly nicer
-                    if id.starts_with("GFDL-") && id.len() < 9 {
-                        v.push((format!("{}-invariants", id), full_name, flags.clone()));
-                    }
+        if flags.is_empty() {
+            flags.push_str("0x0");
+        } else {
+            // Strip the trailing ` | `
+            flags.truncate(flags.len() - 3);
+        }
 
-                    v.push((id.to_owned(), full_name, flags));
-                } else {
-                    bail!("Malformed JSON: {:?}", lic_id);
-                }
+        // `reference` is the canonical spdx.org page for the license, while
+        // `seeAlso` lists other copies of the text; both are useful
+        // attribution links, so we fold them into one URL list.
+        let mut urls = lic.see_also.clone();
+        if let Some(reference) = &lic.reference {
+            if !urls.contains(reference) {
+                urls.insert(0, reference.clone());
             }
+        }
 
-            let name = "NOASSERTION".to_owned();
-            // Add NOASSERTION, which is not yet? part of the SPDX spec
-            // https://github.com/spdx/spdx-spec/issues/50
-            v.push(("NOASSERTION".to_owned(), &name, "0x0".to_owned()));
+        // Add `-invariants` versions of the root GFDL-<version>
+        // licenses so that they work slightly nicer
+        if id.starts_with("GFDL-") && id.len() < 9 {
+            v.push((
+                format!("{}-invariants", id),
+                lic.name.clone(),
+                flags.clone(),
+                category,
+                urls.clone(),
+            ));
+        }
 
-            v.sort_by(|a, b| a.0.cmp(&b.0));
+        license_ids.push((id.to_owned(), lic.details_url.clone()));
+        v.push((id.to_owned(), lic.name.clone(), flags, category, urls));
+    }
 
-            let lic_list_ver = get(&json, "licenseListVersion")?;
-            if let Value::String(ref s) = lic_list_ver {
-                writeln!(identifiers, "pub const VERSION: &str = {:?};", s)?;
-            } else {
-                bail!("Malformed JSON: {:?}", lic_list_ver)
-            }
-            writeln!(identifiers)?;
-            writeln!(identifiers, "pub const LICENSES: &[(&str, &str, u8)] = &[")?;
-            for (id, name, flags) in v.iter() {
-                writeln!(identifiers, "    (\"{}\", r#\"{}\"#, {}),", id, name, flags)?;
-            }
-            writeln!(identifiers, "];")?;
+    // Add NOASSERTION, which is not yet? part of the SPDX spec
+    // https://github.com/spdx/spdx-spec/issues/50
+    v.push((
+        "NOASSERTION".to_owned(),
+        "NOASSERTION".to_owned(),
+        "0x0".to_owned(),
+        Category::Unknown,
+        Vec::new(),
+    ));
 
-            Ok(())
-        },
-        debug,
+    v.sort_by(|a, b| a.0.cmp(&b.0));
+
+    writeln!(
+        identifiers,
+        "pub const VERSION: &str = {:?};",
+        license_list.license_list_version
+    )?;
+    writeln!(
+        identifiers,
+        "pub const RELEASE_DATE: &str = {:?};",
+        license_list.release_date
+    )?;
+    writeln!(identifiers)?;
+    writeln!(identifiers, "pub const LICENSES: &[(&str, &str, u8)] = &[")?;
+    for (id, name, flags, _, _) in v.iter() {
+        writeln!(identifiers, "    (\"{}\", r#\"{}\"#, {}),", id, name, flags)?;
+    }
+    writeln!(identifiers, "];")?;
+    writeln!(identifiers)?;
+    writeln!(
+        identifiers,
+        "/// Parallel to `LICENSES`: the category of the license at the same index."
+    )?;
+    writeln!(identifiers, "pub const CATEGORIES: &[Category] = &[")?;
+    for (_, _, _, category, _) in v.iter() {
+        writeln!(identifiers, "    Category::{:?},", category)?;
+    }
+    writeln!(identifiers, "];")?;
+    writeln!(identifiers)?;
+    writeln!(
+        identifiers,
+        "/// Reference URLs (the spdx.org `reference` page, then `seeAlso`) for the license at the same index in `LICENSES`."
+    )?;
+    writeln!(
+        identifiers,
+        "pub const LICENSE_URLS: &[(&str, &[&str])] = &["
+    )?;
+    for (id, _, _, _, urls) in v.iter() {
+        write!(identifiers, "    (\"{}\", &[", id)?;
+        for url in urls {
+            write!(identifiers, "{:?}, ", url)?;
+        }
+        writeln!(identifiers, "]),")?;
+    }
+    writeln!(identifiers, "];")?;
+    writeln!(identifiers)?;
+    writeln!(
+        identifiers,
+        "\
+/// Returns the reference URLs for the SPDX license `id`, or an empty slice
+/// if `id` is unknown or upstream lists none.
+pub fn license_urls(id: &str) -> &'static [&'static str] {{
+    match LICENSE_URLS.binary_search_by_key(&id, |(i, _)| *i) {{
+        Ok(idx) => LICENSE_URLS[idx].1,
+        Err(_) => &[],
+    }}
+}}
+"
     )?;
 
     writeln!(identifiers)?;
@@ -241,66 +500,90 @@ pub const IS_GNU: u8 = 0x10;
     // valid ones
     writeln!(identifiers, "{}", IMPRECISE)?;
 
+    // Fetch each license's full text and embed a compressed blob of them so
+    // `identify()` can do full-text matching without a network round-trip.
+    let license_texts = fetch_license_texts(&upstream_tag, &license_ids, debug)?;
+    eprintln!("#license texts == {}", license_texts.len());
+    write_license_text_blob(&license_texts, debug)?;
+
     let exceptions_json_uri = format!(
         "https://raw.githubusercontent.com/spdx/license-list-data/{}/json/exceptions.json",
         upstream_tag
     );
 
-    download(
-        &exceptions_json_uri,
-        |json| {
-            let exceptions = get(&json, "exceptions")?;
-            let exceptions = if let Value::Array(ref v) = exceptions {
-                v
+    let exception_list: LicenseList = download(&exceptions_json_uri, strict, debug)?;
+    eprintln!("#exceptions == {}", exception_list.exceptions.len());
+
+    let mut v: Vec<(&str, &str, &Option<String>)> = exception_list
+        .exceptions
+        .iter()
+        .map(|exc| {
+            if debug {
+                eprintln!(
+                    "{:?},{:?},reference={:?},seeAlso={:?}",
+                    exc.license_exception_id, exc.name, exc.reference, exc.see_also
+                );
+            }
+
+            let flags = if exc.is_deprecated_license_id {
+                "IS_DEPRECATED"
             } else {
-                bail!("Malformed JSON: {:?}", exceptions)
+                "0"
             };
-            eprintln!("#exceptions == {}", exceptions.len());
-
-            let mut v = vec![];
-            for exc in exceptions.iter() {
-                let exc = if let Value::Object(m) = exc {
-                    m
-                } else {
-                    bail!("Malformed JSON: {:?}", exc)
-                };
-                if debug {
-                    eprintln!(
-                        "{:?},{:?}",
-                        get(exc, "licenseExceptionId"),
-                        get(exc, "name")
-                    );
-                }
 
-                let lic_exc_id = get(exc, "licenseExceptionId")?;
-                if let Value::String(s) = lic_exc_id {
-                    let flags = match get(exc, "isDeprecatedLicenseId") {
-                        Ok(Value::Bool(val)) => {
-                            if *val {
-                                "IS_DEPRECATED"
-                            } else {
-                                "0"
-                            }
-                        }
-                        _ => "0",
-                    };
+            (exc.license_exception_id.as_str(), flags, &exc.details_url)
+        })
+        .collect();
 
-                    v.push((s, flags));
-                } else {
-                    bail!("Malformed JSON: {:?}", lic_exc_id)
-                };
-            }
+    writeln!(identifiers, "pub const EXCEPTIONS: &[(&str, u8)] = &[")?;
+    v.sort_by_key(|v| v.0);
+    for (exc, flags, _) in v.iter() {
+        writeln!(identifiers, "    (\"{}\", {}),", exc, flags)?;
+    }
+    writeln!(identifiers, "];")?;
 
-            writeln!(identifiers, "pub const EXCEPTIONS: &[(&str, u8)] = &[")?;
-            v.sort_by_key(|v| v.0);
-            for (exc, flags) in v.iter() {
-                writeln!(identifiers, "    (\"{}\", {}),", exc, flags)?;
-            }
-            writeln!(identifiers, "];")?;
+    // `exception_ids` preserves the sort order `v` was just sorted into, so
+    // the result is already sorted by exception id for `binary_search_by_key`.
+    let exception_ids: Vec<(String, Option<String>)> = v
+        .iter()
+        .map(|(id, _, details_url)| ((*id).to_owned(), (*details_url).clone()))
+        .collect();
+    let exception_applies_to = fetch_exception_applies_to(&upstream_tag, &exception_ids, debug)?;
 
-            Ok(())
-        },
-        debug,
+    writeln!(identifiers)?;
+    writeln!(
+        identifiers,
+        "\
+/// Which SPDX license ids each exception is commonly applied with, so a
+/// `License WITH Exception` expression can be flagged as suspicious when
+/// the exception is paired with an unrelated license. Sorted by exception
+/// id; entries with no known applicable licenses are omitted."
+    )?;
+    writeln!(
+        identifiers,
+        "pub const EXCEPTION_APPLIES_TO: &[(&str, &[&str])] = &["
+    )?;
+    for (id, licenses) in exception_applies_to.iter().filter(|(_, l)| !l.is_empty()) {
+        write!(identifiers, "    (\"{}\", &[", id)?;
+        for lic in licenses {
+            write!(identifiers, "{:?}, ", lic)?;
+        }
+        writeln!(identifiers, "]),")?;
+    }
+    writeln!(identifiers, "];")?;
+    writeln!(identifiers)?;
+    writeln!(
+        identifiers,
+        "\
+/// Returns the license ids exception `id` is known to be commonly applied
+/// with, or an empty slice if `id` is unknown or has no known pairing.
+pub fn exception_applies_to(id: &str) -> &'static [&'static str] {{
+    match EXCEPTION_APPLIES_TO.binary_search_by_key(&id, |(i, _)| *i) {{
+        Ok(idx) => EXCEPTION_APPLIES_TO[idx].1,
+        Err(_) => &[],
+    }}
+}}
+"
     )?;
 
     drop(identifiers);